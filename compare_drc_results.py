@@ -5,8 +5,10 @@ Verifies that both tools produce identical results
 
 Usage:
     python3 compare_drc_results.py -c calibre.rpt -i icv.log [-t 0.001]
+    python3 compare_drc_results.py --diff old.rpt new.rpt [-f calibre] [-t 0.001]
 """
 
+import os
 import re
 import argparse
 from collections import defaultdict
@@ -226,6 +228,109 @@ class DRCComparator:
                 abs(v1.y - v2.y) < self.tolerance)
 
 
+class ReportDiffer:
+    """Diff two DRC reports from the same tool (e.g. before and after a fix)"""
+
+    def __init__(self, tolerance: float = 0.001):
+        self.tolerance = tolerance
+
+    def diff(self, old_violations: Dict[str, List[Violation]],
+             new_violations: Dict[str, List[Violation]]) -> Dict:
+        """
+        Classify every violation as new, fixed or unchanged
+
+        Violations are matched one-to-one by rule name and location
+        within tolerance, pairing as many markers as possible regardless
+        of their order in the reports. Returns dict with diff results
+        """
+        results = {
+            'new': [],
+            'fixed': [],
+            'unchanged': [],
+            'rules': []
+        }
+
+        all_rules = set(old_violations.keys()) | set(new_violations.keys())
+
+        for rule in sorted(all_rules):
+            old_list = old_violations.get(rule, [])
+            new_list = new_violations.get(rule, [])
+            pairs = self._match(new_list, old_list)
+
+            matched_old = set(pairs.values())
+            new_viols = [v for i, v in enumerate(new_list) if i not in pairs]
+            unchanged_viols = [v for i, v in enumerate(new_list) if i in pairs]
+            unmatched_old = [v for j, v in enumerate(old_list) if j not in matched_old]
+
+            results['new'].extend(new_viols)
+            results['fixed'].extend(unmatched_old)
+            results['unchanged'].extend(unchanged_viols)
+            results['rules'].append({
+                'rule': rule,
+                'new': len(new_viols),
+                'fixed': len(unmatched_old),
+                'unchanged': len(unchanged_viols)
+            })
+
+        return results
+
+    def _match(self, new_viols: List[Violation],
+               old_viols: List[Violation]) -> Dict[int, int]:
+        """
+        Pair new and old violations one-to-one within tolerance
+
+        Uses augmenting paths (maximum bipartite matching) so that a
+        marker taken by one new violation is handed over when another
+        has no other partner. Candidates are tried nearest first.
+        Returns dict of new index -> old index
+        """
+        # Old candidates within tolerance for each new violation
+        candidates = []
+        for new_viol in new_viols:
+            in_range = []
+            for j, old_viol in enumerate(old_viols):
+                dx = abs(new_viol.x - old_viol.x)
+                dy = abs(new_viol.y - old_viol.y)
+                if dx < self.tolerance and dy < self.tolerance:
+                    in_range.append((max(dx, dy), j))
+            candidates.append([j for _, j in sorted(in_range)])
+
+        new_to_old = {}
+        old_to_new = {}
+
+        for start in range(len(new_viols)):
+            # Breadth-first search for a path ending at a free old marker
+            parent = {}
+            queue = [start]
+            free_old = None
+            while queue and free_old is None:
+                i = queue.pop(0)
+                for j in candidates[i]:
+                    if j in parent:
+                        continue
+                    parent[j] = i
+                    if j not in old_to_new:
+                        free_old = j
+                        break
+                    queue.append(old_to_new[j])
+
+            if free_old is None:
+                continue
+
+            # Flip the path: each new violation takes the old marker after it
+            j = free_old
+            while True:
+                i = parent[j]
+                previous = new_to_old.get(i)
+                new_to_old[i] = j
+                old_to_new[j] = i
+                if i == start:
+                    break
+                j = previous
+
+        return new_to_old
+
+
 def print_report(results: Dict):
     """Print detailed comparison report"""
 
@@ -292,6 +397,110 @@ def print_report(results: Dict):
     return results['perfect_match']
 
 
+def print_diff_report(results: Dict, verbose: bool = False):
+    """Print new/fixed/unchanged diff report"""
+
+    print("=" * 80)
+    print("DRC REPORT DIFF (OLD vs NEW)")
+    print("=" * 80)
+    print()
+
+    # Summary statistics
+    print("SUMMARY STATISTICS")
+    print("-" * 80)
+    print(f"New violations:             {len(results['new'])}")
+    print(f"Fixed violations:           {len(results['fixed'])}")
+    print(f"Unchanged violations:       {len(results['unchanged'])}")
+    print()
+
+    # Per-rule breakdown
+    if results['rules']:
+        print("PER-RULE BREAKDOWN")
+        print("-" * 80)
+        print(f"   {'Rule':30s} {'New':>7s} {'Fixed':>7s} {'Same':>7s}")
+        for item in results['rules']:
+            print(f"   {item['rule']:30s} {item['new']:7d} {item['fixed']:7d} "
+                  f"{item['unchanged']:7d}")
+        print()
+
+    # Violation locations
+    if verbose:
+        if results['new']:
+            print("❌ NEW VIOLATIONS")
+            print("-" * 80)
+            for viol in results['new']:
+                print(f"   {viol.rule:30s} ({viol.x:.3f}, {viol.y:.3f})")
+            print()
+
+        if results['fixed']:
+            print("✅ FIXED VIOLATIONS")
+            print("-" * 80)
+            for viol in results['fixed']:
+                print(f"   {viol.rule:30s} ({viol.x:.3f}, {viol.y:.3f})")
+            print()
+
+    # Overall result
+    print("=" * 80)
+    if not results['new']:
+        print("✅ NO NEW VIOLATIONS")
+    else:
+        print("❌ NEW VIOLATIONS FOUND")
+        if not verbose:
+            print("Rerun with -v to list their locations.")
+    print("=" * 80)
+
+    return not results['new']
+
+
+def run_diff(args):
+    """Diff two reports of the same format"""
+    old_file, new_file = args.diff
+
+    # Input errors must not look like new violations (exit 1)
+    for filename in (old_file, new_file):
+        if not os.path.isfile(filename):
+            print(f"Error: File not found: {filename}")
+            sys.exit(2)
+
+    report_format = args.format or 'calibre'
+    report_parser = CalibreParser() if report_format == 'calibre' else ICVParser()
+
+    if args.verbose:
+        print(f"Parsing old results from: {old_file}")
+    old_violations = report_parser.parse_file(old_file)
+
+    if args.verbose:
+        print(f"  Found {sum(len(v) for v in old_violations.values())} violations "
+              f"in {len(old_violations)} rules")
+        print(f"Parsing new results from: {new_file}")
+    new_violations = report_parser.parse_file(new_file)
+
+    if args.verbose:
+        print(f"  Found {sum(len(v) for v in new_violations.values())} violations "
+              f"in {len(new_violations)} rules")
+        print()
+
+    # A wrong -f or a truncated report parses to nothing and would
+    # otherwise show every old violation as fixed
+    if not old_violations:
+        print(f"Warning: No violations parsed from {old_file} "
+              f"(is -f {report_format} the right format?)")
+
+    if not new_violations:
+        print(f"Error: No violations parsed from {new_file} "
+              f"(is -f {report_format} the right format?)")
+        print("Refusing to report an unparsed or empty report as clean.")
+        sys.exit(2)
+
+    differ = ReportDiffer(tolerance=args.tolerance)
+    results = differ.diff(old_violations, new_violations)
+
+    clean = print_diff_report(results, args.verbose)
+
+    # Exit code
+    sys.exit(0 if clean else 1)
+
+
 def main():
     parser = argparse.ArgumentParser(
         description='Compare Calibre and IC Validator DRC results',
@@ -306,21 +515,39 @@ Examples:
 
   # Verbose output
   python3 compare_drc_results.py -c calibre.rpt -i icv.log -v
+
+  # New/fixed/unchanged violations between two runs of the same tool
+  python3 compare_drc_results.py --diff old.rpt new.rpt -f calibre
+
+Exit codes for --diff: 0 = no new violations, 1 = new violations,
+2 = usage error, missing input file, or no violations parsed from NEW
         """
     )
 
     parser.add_argument(
         '-c', '--calibre',
-        required=True,
         help='Calibre DRC report file'
     )
 
     parser.add_argument(
         '-i', '--icv',
-        required=True,
         help='IC Validator log/report file'
     )
 
+    parser.add_argument(
+        '--diff',
+        nargs=2,
+        metavar=('OLD', 'NEW'),
+        help='Diff two reports of the same format instead of comparing tools '
+             '(Calibre ASCII or ICV text reports; JSON is not supported)'
+    )
+
+    parser.add_argument(
+        '-f', '--format',
+        choices=['calibre', 'icv'],
+        help='Report format for --diff (default: calibre)'
+    )
+
     parser.add_argument(
         '-t', '--tolerance',
         type=float,
@@ -336,6 +563,20 @@ Examples:
 
     args = parser.parse_args()
 
+    if args.tolerance <= 0:
+        parser.error('-t/--tolerance must be greater than 0')
+
+    if args.diff:
+        if args.calibre or args.icv:
+            parser.error('-c/--calibre and -i/--icv cannot be used with --diff')
+        run_diff(args)
+
+    if args.format:
+        parser.error('-f/--format is only valid with --diff')
+
+    if not args.calibre or not args.icv:
+        parser.error('-c/--calibre and -i/--icv are required unless --diff is given')
+
     if args.verbose:
         print(f"Parsing Calibre results from: {args.calibre}")
 
@@ -0,0 +1,152 @@
+#!/usr/bin/env python3
+"""
+Regression checks for compare_drc_results.py --diff
+
+Usage:
+    python3 -m unittest test_compare_drc_results
+"""
+
+import os
+import subprocess
+import sys
+import tempfile
+import unittest
+
+from compare_drc_results import ReportDiffer, Violation
+
+SCRIPT = os.path.join(os.path.dirname(os.path.abspath(__file__)),
+                      'compare_drc_results.py')
+
+OLD_REPORT = """RULECHECK M1_SPACE
+POLYGON ( 10.0 20.0 ) ( 11.0 21.0 )
+POLYGON ( 30.0 40.0 ) ( 31.0 41.0 )
+"""
+
+NEW_REPORT = """RULECHECK M1_SPACE
+POLYGON ( 10.0004 20.0 ) ( 11.0 21.0 )
+POLYGON ( 50.0 60.0 ) ( 51.0 61.0 )
+"""
+
+ICV_LOG = "M1_SPACE violation at 10.0, 20.0\n"
+
+
+class ReportDifferTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.old = self._write('old.rpt', OLD_REPORT)
+        self.new = self._write('new.rpt', NEW_REPORT)
+
+    def tearDown(self):
+        self.tmpdir.cleanup()
+
+    def _write(self, name, content):
+        path = os.path.join(self.tmpdir.name, name)
+        with open(path, 'w') as f:
+            f.write(content)
+        return path
+
+    def _run(self, *args):
+        return subprocess.run([sys.executable, SCRIPT] + list(args),
+                              capture_output=True, text=True)
+
+    # ------------------------------------------------------------------
+    # Matching
+    # ------------------------------------------------------------------
+
+    def test_sub_nm_tolerance_matches_near_coincident_markers(self):
+        # Markers 0.8nm apart are within Violation.__eq__'s fixed 1nm
+        # tolerance, but distinct under -t 0.0001
+        old = {'M1_SPACE': [Violation('M1_SPACE', 0.0, 0.0, 'polygon'),
+                            Violation('M1_SPACE', 0.0008, 0.0, 'polygon')]}
+        new = {'M1_SPACE': [Violation('M1_SPACE', 0.0008, 0.0, 'polygon'),
+                            Violation('M1_SPACE', 0.0, 0.0, 'polygon')]}
+
+        results = ReportDiffer(tolerance=0.0001).diff(old, new)
+
+        self.assertEqual(len(results['new']), 0)
+        self.assertEqual(len(results['fixed']), 0)
+        self.assertEqual(len(results['unchanged']), 2)
+
+    def test_shifted_markers_pair_regardless_of_order(self):
+        # The nearest pair (0.0005 -> 0.0009) would leave 0.0014 unmatched;
+        # both new markers can still be paired within tolerance
+        old = {'M1_SPACE': [Violation('M1_SPACE', 0.0, 0.0, 'polygon'),
+                            Violation('M1_SPACE', 0.0009, 0.0, 'polygon')]}
+        new = {'M1_SPACE': [Violation('M1_SPACE', 0.0005, 0.0, 'polygon'),
+                            Violation('M1_SPACE', 0.0014, 0.0, 'polygon')]}
+
+        for new_order in (new, {'M1_SPACE': new['M1_SPACE'][::-1]}):
+            results = ReportDiffer(tolerance=0.001).diff(old, new_order)
+
+            self.assertEqual(len(results['new']), 0)
+            self.assertEqual(len(results['fixed']), 0)
+            self.assertEqual(len(results['unchanged']), 2)
+
+    def test_new_and_fixed_classification(self):
+        old = {'M1_SPACE': [Violation('M1_SPACE', 10.0, 20.0, 'polygon'),
+                            Violation('M1_SPACE', 30.0, 40.0, 'polygon')]}
+        new = {'M1_SPACE': [Violation('M1_SPACE', 10.0004, 20.0, 'polygon'),
+                            Violation('M1_SPACE', 50.0, 60.0, 'polygon')]}
+
+        results = ReportDiffer(tolerance=0.001).diff(old, new)
+
+        self.assertEqual([(v.x, v.y) for v in results['new']], [(50.0, 60.0)])
+        self.assertEqual([(v.x, v.y) for v in results['fixed']], [(30.0, 40.0)])
+        self.assertEqual(len(results['unchanged']), 1)
+
+    # ------------------------------------------------------------------
+    # Exit codes: 0 = no new, 1 = new, 2 = usage/input error
+    # ------------------------------------------------------------------
+
+    def test_clean_diff_exits_0(self):
+        self.assertEqual(self._run('--diff', self.old, self.old).returncode, 0)
+
+    def test_new_violations_exit_1(self):
+        self.assertEqual(self._run('--diff', self.old, self.new).returncode, 1)
+
+    def test_missing_input_exits_2(self):
+        missing = os.path.join(self.tmpdir.name, 'missing.rpt')
+        self.assertEqual(self._run('--diff', self.old, missing).returncode, 2)
+        self.assertEqual(self._run('--diff', missing, self.new).returncode, 2)
+
+    def test_wrong_format_new_report_exits_2(self):
+        icv_log = self._write('icv.log', ICV_LOG)
+        result = self._run('--diff', self.old, icv_log)
+        self.assertEqual(result.returncode, 2)
+        self.assertIn('No violations parsed', result.stdout)
+
+    def test_empty_new_report_exits_2(self):
+        empty = self._write('empty.rpt', '')
+        self.assertEqual(self._run('--diff', self.old, empty).returncode, 2)
+
+    def test_wrong_format_old_report_warns(self):
+        icv_log = self._write('icv.log', ICV_LOG)
+        result = self._run('--diff', icv_log, self.new)
+        self.assertEqual(result.returncode, 1)
+        self.assertIn('Warning: No violations parsed', result.stdout)
+
+    # ------------------------------------------------------------------
+    # Option validation
+    # ------------------------------------------------------------------
+
+    def test_diff_rejects_calibre_and_icv_options(self):
+        for option in ('-c', '-i'):
+            result = self._run('--diff', self.old, self.new, option, self.old)
+            self.assertEqual(result.returncode, 2)
+            self.assertIn('cannot be used with --diff', result.stderr)
+
+    def test_format_requires_diff(self):
+        result = self._run('-c', self.old, '-i', self.new, '-f', 'icv')
+        self.assertEqual(result.returncode, 2)
+        self.assertIn('only valid with --diff', result.stderr)
+
+    def test_non_positive_tolerance_rejected(self):
+        for tolerance in ('0', '-1'):
+            result = self._run('--diff', self.old, self.old, '-t', tolerance)
+            self.assertEqual(result.returncode, 2)
+            self.assertIn('must be greater than 0', result.stderr)
+
+
+if __name__ == '__main__':
+    unittest.main()